@@ -0,0 +1,118 @@
+//! A queue whose consumer can wait for elements asynchronously. This module is only available
+//! with both the `futures` and `std` features, since the queue is guarded by a [`Mutex`].
+
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use futures_core::Stream;
+
+use crate::ArrayQueue;
+
+/// An [`ArrayQueue`] behind a mutex, which can be pushed to through a shared reference and
+/// consumed as a [`Stream`].
+///
+/// The stream is implemented for `&AsyncQueue`, and yields elements in FIFO order. When the queue
+/// is empty, polling the stream registers the task to be woken by the next push. Only one task is
+/// woken per push, so the queue is meant to have a single consumer.
+pub struct AsyncQueue<T, const C: usize> {
+    inner: Mutex<Inner<T, C>>,
+}
+
+struct Inner<T, const C: usize> {
+    queue: ArrayQueue<T, C>,
+
+    // the task waiting for the next element, if the consumer found the queue empty
+    waker: Option<Waker>,
+}
+
+impl<T, const C: usize> Default for AsyncQueue<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const C: usize> AsyncQueue<T, C> {
+    /// Creates a new empty AsyncQueue.
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                queue: ArrayQueue::new(),
+                waker: None,
+            }),
+        }
+    }
+
+    /// Locks the queue. A panic while the lock was held can't leave the queue in an invalid state,
+    /// so poisoning is ignored.
+    fn lock(&self) -> MutexGuard<'_, Inner<T, C>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Pushs an element to the queue, waking the consumer if it is waiting. If the queue is full,
+    /// Err(T) is returned.
+    #[must_use = "if the queue is full, the value is returned and must be handled"]
+    pub fn push(&self, value: T) -> Result<(), T> {
+        let waker = {
+            let mut inner = self.lock();
+            inner.queue.push(value)?;
+            inner.waker.take()
+        };
+
+        // wake outside the lock, so the consumer doesn't wake up just to block on it
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+
+    /// Pops an element from the queue without waiting.
+    pub fn try_pop(&self) -> Option<T> {
+        self.lock().queue.pop()
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> usize {
+        self.lock().queue.len()
+    }
+
+    /// Returns true if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().queue.is_empty()
+    }
+
+    /// Returns true if the queue is full.
+    pub fn is_full(&self) -> bool {
+        self.lock().queue.is_full()
+    }
+}
+
+/// Moves the elements of an [`ArrayQueue`] into a new AsyncQueue, in FIFO order.
+impl<T, const C: usize> From<ArrayQueue<T, C>> for AsyncQueue<T, C> {
+    fn from(queue: ArrayQueue<T, C>) -> Self {
+        Self {
+            inner: Mutex::new(Inner { queue, waker: None }),
+        }
+    }
+}
+
+/// Yields the elements of the queue in FIFO order. The stream never ends; it is pending while the
+/// queue is empty.
+impl<T, const C: usize> Stream for &AsyncQueue<T, C> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut inner = self.lock();
+        if let Some(value) = inner.queue.pop() {
+            return Poll::Ready(Some(value));
+        }
+
+        // the lock is still held, so a push can't slip in between the pop and the registration
+        match &mut inner.waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            waker => *waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
@@ -0,0 +1,43 @@
+//! The errors returned by fallible queue operations.
+//!
+//! Methods that only fail when the queue is full, like [`push`](crate::ArrayQueue::push), return
+//! the rejected element directly in `Err(T)`. [`PushError`] wraps that element for use with `?`,
+//! and [`CapacityError`] is returned when a queue is built from more elements than it can hold.
+
+use core::fmt;
+
+/// The error returned when a queue is given more elements than it can hold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The number of elements the queue was asked to hold.
+    pub requested: usize,
+
+    /// The capacity of the queue.
+    pub capacity: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot hold {} elements in a queue with capacity {}",
+            self.requested, self.capacity
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// The error returned when pushing to a full queue. It holds the element that could not be pushed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PushError<T>(pub T);
+
+impl<T> fmt::Display for PushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cannot push to a full queue")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for PushError<T> {}
@@ -1,11 +1,54 @@
-use std::mem::{MaybeUninit, replace};
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FusedIterator, Sum};
+use core::mem::{self, MaybeUninit, replace};
+use core::ops::{Index, IndexMut, Range};
+use core::ptr;
+use core::slice;
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::VecDeque, vec::Vec};
+
+pub mod error;
+pub mod spsc;
+
+#[cfg(all(feature = "futures", feature = "std"))]
+pub mod async_queue;
+
+#[cfg(feature = "std")]
+pub mod sync_queue;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+pub use error::{CapacityError, PushError};
 
 /// A queue that allocates its elements on the stack. It uses a primitive array with pointers to
 /// the head and tail of the queue. The queue is empty if the head and tail pointers are equal.
 ///
 /// The queue is different from VecDeque in that attempting to add an element to a full queue will
 /// simply return the element back.
-#[derive(Debug)]
+///
+/// The queue is `Send` only if `T` is `Send`, and `Sync` only if `T` is `Sync`:
+///
+/// ```compile_fail,E0277
+/// fn is_send<S: Send>() {}
+/// is_send::<queue::ArrayQueue<std::rc::Rc<u8>, 4>>();
+/// ```
+///
+/// ```compile_fail,E0277
+/// fn is_sync<S: Sync>() {}
+/// is_sync::<queue::ArrayQueue<std::cell::Cell<u8>, 4>>();
+/// ```
 pub struct ArrayQueue<T, const C: usize> {
     data: [MaybeUninit<T>; C],
     head: usize,
@@ -13,6 +56,18 @@ pub struct ArrayQueue<T, const C: usize> {
     len: usize,
 }
 
+// there are no manual `Send` or `Sync` impls; the queue gets them from `T` through the buffer. these
+// and the `compile_fail` examples on `ArrayQueue` fail if that ever stops being the case
+fn _assert_send<T: Send, const C: usize>() {
+    fn is_send<S: Send>() {}
+    is_send::<ArrayQueue<T, C>>();
+}
+
+fn _assert_sync<T: Sync, const C: usize>() {
+    fn is_sync<S: Sync>() {}
+    is_sync::<ArrayQueue<T, C>>();
+}
+
 impl<T, const C: usize> Default for ArrayQueue<T, C> {
     fn default() -> Self {
         Self::new()
@@ -20,63 +75,232 @@ impl<T, const C: usize> Default for ArrayQueue<T, C> {
 }
 
 impl<T, const C: usize> ArrayQueue<T, C> {
+    /// The maximum number of elements the queue can hold.
+    pub const CAPACITY: usize = C;
+
+    /// Fails to compile when evaluated for a queue with zero capacity, which could never hold an
+    /// element. Every constructor evaluates it.
+    const NONZERO_CAPACITY: () = assert!(C > 0, "ArrayQueue capacity must be greater than zero");
+
     /// Creates a new empty ArrayQueue.
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
+        let () = Self::NONZERO_CAPACITY;
         Self {
-            // data: MaybeUninit::uninit_array(),
-            data: unsafe { MaybeUninit::uninit().assume_init() },
+            data: [const { MaybeUninit::uninit() }; C],
             head: 0,
             tail: 0,
             len: 0,
         }
     }
 
+    /// Creates a full ArrayQueue from an array. The elements are popped in array order.
+    pub fn from_array(arr: [T; C]) -> Self {
+        let () = Self::NONZERO_CAPACITY;
+        Self {
+            data: arr.map(MaybeUninit::new),
+            head: 0,
+            tail: 0,
+            len: C,
+        }
+    }
+
+    /// Creates a full ArrayQueue where the element at index `i` is `f(i)`, with index 0 at the
+    /// front of the queue. If `f` panics, the elements created so far are dropped.
+    pub fn from_fn<F: FnMut(usize) -> T>(f: F) -> Self {
+        let () = Self::NONZERO_CAPACITY;
+
+        // build the elements as a plain array first, which drops them if `f` panics
+        Self::from_array(core::array::from_fn(f))
+    }
+
+    /// Creates a full ArrayQueue where every element is all zeroes.
+    #[cfg(feature = "bytemuck")]
+    pub const fn zeroed() -> Self
+    where
+        T: bytemuck::Zeroable,
+    {
+        let () = Self::NONZERO_CAPACITY;
+        Self {
+            // an all-zero T is valid, so every slot is initialized
+            data: [const { MaybeUninit::zeroed() }; C],
+            head: 0,
+            tail: 0,
+            len: C,
+        }
+    }
+
+    /// Creates an ArrayQueue from clones of the elements of a slice, in order. Fails if the slice
+    /// has more than `C` elements.
+    pub fn try_from_slice(src: &[T]) -> Result<Self, CapacityError>
+    where
+        T: Clone,
+    {
+        if src.len() > C {
+            return Err(CapacityError {
+                requested: src.len(),
+                capacity: C,
+            });
+        }
+
+        Ok(src.iter().cloned().collect())
+    }
+
     /// Returns true if the queue is empty.
-    fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.len == 0
     }
 
+    /// Returns true if the queue is full.
+    pub const fn is_full(&self) -> bool {
+        self.len == C
+    }
+
     /// Returns the number of elements in the queue.
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         self.len
     }
 
-    /// Clears the queue of all elements.
-    pub fn clear(&mut self) {
-        if self.is_empty() {
-            return;
+    /// Returns the maximum number of elements the queue can hold.
+    pub const fn capacity(&self) -> usize {
+        C
+    }
+
+    /// Returns the number of elements that can be pushed before the queue is full.
+    pub const fn remaining_capacity(&self) -> usize {
+        C - self.len
+    }
+
+    /// Returns the internal `(head, tail, len)` offsets of the queue, for inspecting the state of
+    /// the ring buffer. Head and tail are wrapped lazily, so either may be equal to `C`. These are
+    /// implementation details and may change between versions.
+    #[cfg(feature = "debug-internals")]
+    pub fn raw_offsets(&self) -> (usize, usize, usize) {
+        (self.head, self.tail, self.len)
+    }
+
+    /// Overwrites the internal `(head, tail, len)` offsets of the queue. This is meant for testing
+    /// how code handles a corrupted queue, such as with [`ArrayQueue::check_invariants`].
+    ///
+    /// # Safety
+    ///
+    /// Until the offsets are made consistent again, with the `len` slots starting at `tail`
+    /// initialized, the queue must not be dropped or used other than through
+    /// [`ArrayQueue::raw_offsets`] and [`ArrayQueue::check_invariants`].
+    #[cfg(feature = "debug-internals")]
+    pub unsafe fn set_raw_offsets(&mut self, head: usize, tail: usize, len: usize) {
+        self.head = head;
+        self.tail = tail;
+        self.len = len;
+    }
+
+    /// Panics if the internal offsets of the queue are inconsistent. `len` must be at most `C`,
+    /// `head` and `tail` must be at most `C` (they are wrapped lazily), and `head` must be `len`
+    /// slots ahead of `tail`, wrapping around the end of the buffer.
+    ///
+    /// Every method that moves the offsets runs this check in debug builds.
+    #[cfg(feature = "debug-internals")]
+    pub fn check_invariants(&self) {
+        self.assert_invariants();
+    }
+
+    /// Runs [`ArrayQueue::assert_invariants`] if debug assertions are enabled.
+    #[inline]
+    fn debug_check_invariants(&self) {
+        if cfg!(debug_assertions) {
+            self.assert_invariants();
         }
+    }
 
-        unsafe {
-            // [x, x, T, ., ., H, x, x]
-            // or
-            // [., H, x, x, T, ., ., .]
-            //
-            // drop elements from tail to the head pointer / end of the buffer
-            let end = if self.tail < self.head { self.head } else { C };
-
-            for i in self.tail..end { // range will be empty if head is behind tail
-                self.data[i].assume_init_drop();
-            }
+    /// Panics if the internal offsets of the queue are inconsistent.
+    fn assert_invariants(&self) {
+        let (head, tail, len) = (self.head, self.tail, self.len);
+        assert!(len <= C, "len (is {len}) should be <= capacity (is {C})");
+        assert!(head <= C, "head (is {head}) should be <= capacity (is {C})");
+        assert!(tail <= C, "tail (is {tail}) should be <= capacity (is {C})");
+        assert!(
+            head % C == (tail + len) % C,
+            "head (is {head}) should be len (is {len}) slots ahead of tail (is {tail})",
+        );
+    }
 
-            // [., ., H, x, x, T, ., .]
-            //
-            // if head is behind tail, drop elements from head to start of the buffer
-            if self.tail >= self.head {
-                for i in 0..self.head {
-                    self.data[i].assume_init_drop();
-                }
-            }
+    /// Returns the ranges of `data` that hold the elements at logical indices `start..end`, in FIFO
+    /// order. The second range is empty unless the elements wrap around the end of the buffer.
+    ///
+    /// Indices past `len` refer to the free slots after the back of the queue.
+    fn slice_ranges(&self, start: usize, end: usize) -> (Range<usize>, Range<usize>) {
+        debug_assert!(start <= end && end <= C);
+
+        if start == end {
+            return (0..0, 0..0);
         }
 
+        let first = (self.tail + start) % C;
+        let last = first + (end - start);
+
+        if last <= C {
+            // [., T, x, x, H, .]
+            (first..last, 0..0)
+        } else {
+            // [x, H, ., ., T, x]
+            (first..C, 0..last - C)
+        }
+    }
+
+    /// Clears the queue of all elements.
+    ///
+    /// The queue is emptied before any element is dropped. If dropping an element panics, the
+    /// queue is left empty and the remaining elements are still dropped.
+    ///
+    /// This takes O(1) time if `T` doesn't need to be dropped, which includes all `Copy` types.
+    pub fn clear(&mut self) {
+        // [x, x, T, ., ., H, x, x]
+        // or
+        // [., H, x, x, T, ., ., .]
+        //
+        // the elements run from tail to the head pointer / end of the buffer, then from the start
+        // of the buffer to head if head is behind tail
+        let (first, second) = self.slice_ranges(0, self.len);
+
         self.head = 0;
         self.tail = 0;
         self.len = 0;
+        self.debug_check_invariants();
+
+        unsafe { drop_ranges(&mut self.data, first, second) };
+    }
+
+    /// Clears the queue, passing each element to `f` in FIFO order instead of dropping it.
+    pub fn clear_with<F: FnMut(T)>(&mut self, mut f: F) {
+        while let Some(value) = self.pop() {
+            f(value);
+        }
+
+        self.head = 0;
+        self.tail = 0;
+    }
+
+    /// Takes all elements out of the queue, returning them in a new queue and leaving this one
+    /// empty. No element is cloned, dropped, or visited one at a time, but the whole backing array
+    /// is copied, whatever the length of the queue.
+    ///
+    /// This isn't named `take`, since that would be shadowed by `io::Read::take` for byte
+    /// queues.
+    pub fn take_all(&mut self) -> Self {
+        mem::take(self)
+    }
+
+    /// Swaps the elements of this queue with the elements of `other`, so that each queue ends up
+    /// with the other's elements in their original order. No element is cloned, dropped, or
+    /// visited one at a time, but both whole backing arrays are copied, whatever the lengths of the
+    /// queues.
+    pub fn swap_contents(&mut self, other: &mut Self) {
+        mem::swap(self, other);
     }
 
     /// Pushs an element to the queue. If the queue is full, Err(T) is returned.
+    #[must_use = "if the queue is full, the value is returned and must be handled"]
     pub fn push(&mut self, value: T) -> Result<(), T> {
-        if self.len() == C {
+        if self.is_full() {
             return Err(value);
         }
 
@@ -84,10 +308,116 @@ impl<T, const C: usize> ArrayQueue<T, C> {
         self.data[self.head].write(value);
         self.head += 1;
         self.len += 1;
+        self.debug_check_invariants();
+
+        Ok(())
+    }
+
+    /// Pushs the element returned by `f` to the queue. `f` is only called if there is room for the
+    /// element; if the queue is full, `f` is returned in Err(F) without being called.
+    #[must_use = "if the queue is full, the closure is returned and must be handled"]
+    pub fn push_with<F: FnOnce() -> T>(&mut self, f: F) -> Result<(), F> {
+        if self.is_full() {
+            return Err(f);
+        }
+
+        self.head %= C;
+        self.data[self.head].write(f());
+        self.head += 1;
+        self.len += 1;
+        self.debug_check_invariants();
 
         Ok(())
     }
 
+    /// Pushs elements from the iterator until the queue is full, and returns the iterator with the
+    /// elements that did not fit.
+    ///
+    /// The queue is checked for room before each element is taken from the iterator, so no element
+    /// is lost: the first element of the returned iterator is the one that would have been pushed
+    /// next.
+    #[must_use = "the elements that did not fit are left in the returned iterator"]
+    pub fn push_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) -> I::IntoIter {
+        let mut iter = iter.into_iter();
+        while !self.is_full() {
+            match iter.next() {
+                Some(value) => {
+                    let _ = self.push(value);
+                }
+                None => break,
+            }
+        }
+        iter
+    }
+
+    /// Pushs an element to the queue. If the queue is full, the oldest element is removed to make
+    /// room and returned.
+    #[must_use = "if the queue is full, the evicted element is returned and must be handled"]
+    pub fn force_push(&mut self, value: T) -> Option<T> {
+        let evicted = if self.is_full() { self.pop() } else { None };
+
+        // there is always room after evicting
+        let _ = self.push(value);
+        evicted
+    }
+
+    /// Pushs an element to the queue, and returns whether it was pushed. If the queue is full, the
+    /// element is dropped.
+    #[must_use = "if the queue is full, the value is dropped"]
+    pub fn saturating_push(&mut self, value: T) -> bool {
+        self.push(value).is_ok()
+    }
+
+    /// Pushs as many elements from the slice as fit in the queue, and returns the number of
+    /// elements pushed.
+    #[must_use = "the elements of the slice past the returned count were not pushed"]
+    pub fn push_slice(&mut self, src: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = src.len().min(C - self.len);
+        if count == 0 {
+            return 0;
+        }
+
+        // the free slots after head, which may wrap around to the start of the buffer
+        let (first, second) = self.slice_ranges(self.len, self.len + count);
+        let (src_first, src_second) = src[..count].split_at(first.len());
+        self.data[first].copy_from_slice(slice_as_uninit(src_first));
+        self.data[second].copy_from_slice(slice_as_uninit(src_second));
+        self.len += count;
+        self.head = (self.tail + self.len) % C;
+        self.debug_check_invariants();
+
+        count
+    }
+
+    /// Pushs clones of as many elements from the slice as fit in the queue, and returns the number
+    /// of elements pushed. For `Copy` elements, [`ArrayQueue::push_slice`] is faster.
+    #[must_use = "the elements of the slice past the returned count were not pushed"]
+    pub fn extend_from_slice(&mut self, src: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        let count = src.len().min(C - self.len);
+        self.extend(src[..count].iter().cloned());
+        count
+    }
+
+    /// Moves elements from the front of `other` to the back of this queue until this queue is full
+    /// or `other` is empty. The queues may have different capacities; any elements that do not fit
+    /// are left in `other`, in their original order.
+    pub fn append<const C2: usize>(&mut self, other: &mut ArrayQueue<T, C2>) {
+        while !self.is_full() {
+            match other.pop() {
+                Some(value) => {
+                    let _ = self.push(value);
+                }
+                None => break,
+            }
+        }
+    }
+
     /// Pops an element from the queue.
     pub fn pop(&mut self) -> Option<T> {
         if self.is_empty() {
@@ -98,13 +428,1306 @@ impl<T, const C: usize> ArrayQueue<T, C> {
         let res = replace(&mut self.data[self.tail], MaybeUninit::uninit());
         self.tail += 1;
         self.len -= 1;
+        self.debug_check_invariants();
 
         unsafe { Some(res.assume_init()) }
     }
-}
 
-impl<T, const C: usize> Drop for ArrayQueue<T, C> {
-    fn drop(&mut self) {
-        self.clear();
+    /// Pushs an element to the queue. If the queue is full, a [`PushError`] holding the element is
+    /// returned, which can be propagated with `?`.
+    #[must_use = "if the queue is full, the value is returned and must be handled"]
+    pub fn try_push(&mut self, value: T) -> Result<(), PushError<T>> {
+        self.push(value).map_err(PushError)
     }
-}
+
+    /// Pushs an element to the front of the queue, so that it is the next to be popped. If the queue
+    /// is full, Err(T) is returned.
+    #[must_use = "if the queue is full, the value is returned and must be handled"]
+    pub fn push_front(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+
+        // step tail back, wrapping to the end of the buffer if it is at the start
+        self.tail = if self.tail == 0 { C - 1 } else { self.tail - 1 };
+        self.data[self.tail].write(value);
+        self.len += 1;
+        self.debug_check_invariants();
+
+        Ok(())
+    }
+
+    /// Pops the most recently pushed element from the back of the queue.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // step head back, wrapping to the end of the buffer if it is at the start
+        self.head = if self.head == 0 { C - 1 } else { self.head - 1 };
+        let res = replace(&mut self.data[self.head], MaybeUninit::uninit());
+        self.len -= 1;
+        self.debug_check_invariants();
+
+        unsafe { Some(res.assume_init()) }
+    }
+
+    /// Pops as many elements into the slice as it can hold, and returns the number of elements
+    /// popped.
+    #[must_use = "only the elements of the slice before the returned count were popped into"]
+    pub fn pop_slice(&mut self, dst: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = dst.len().min(self.len);
+        if count == 0 {
+            return 0;
+        }
+
+        let (first, second) = self.slice_ranges(0, count);
+        let (dst_first, dst_second) = dst[..count].split_at_mut(first.len());
+        unsafe {
+            dst_first.copy_from_slice(slice_assume_init_ref(&self.data[first]));
+            dst_second.copy_from_slice(slice_assume_init_ref(&self.data[second]));
+        }
+        self.tail = (self.tail + count) % C;
+        self.len -= count;
+        self.debug_check_invariants();
+
+        count
+    }
+
+    /// Pops as many elements into the slice as it can hold, and returns the number of elements
+    /// popped.
+    ///
+    /// This is the same as [`pop_slice`](Self::pop_slice), named for draining in batches: the
+    /// elements are copied in at most two bulk copies, one per contiguous part of the buffer, and
+    /// the offsets are updated once, which is much faster than calling [`pop`](Self::pop) in a
+    /// loop.
+    #[must_use = "only the elements of the slice before the returned count were popped into"]
+    pub fn pop_batch(&mut self, dst: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        self.pop_slice(dst)
+    }
+
+    /// Pops as many elements from the back of the queue into the slice as it can hold, newest
+    /// first, and returns the number of elements popped.
+    #[must_use = "only the elements of the slice before the returned count were popped into"]
+    pub fn pop_back_into(&mut self, dst: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = dst.len().min(self.len);
+        if count == 0 {
+            return 0;
+        }
+
+        // copy the elements in FIFO order, then flip them so the newest comes first
+        let (first, second) = self.slice_ranges(self.len - count, self.len);
+        let (dst_first, dst_second) = dst[..count].split_at_mut(first.len());
+        unsafe {
+            dst_first.copy_from_slice(slice_assume_init_ref(&self.data[first]));
+            dst_second.copy_from_slice(slice_assume_init_ref(&self.data[second]));
+        }
+        dst[..count].reverse();
+
+        self.len -= count;
+        self.head = (self.tail + self.len) % C;
+        self.debug_check_invariants();
+
+        count
+    }
+
+    /// Returns a reference to the element that would be popped next, without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // tail may be equal to C if the last pop was at the end of the buffer
+        unsafe { Some(self.data[self.tail % C].assume_init_ref()) }
+    }
+
+    /// Returns a mutable reference to the element that would be popped next, without removing it.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        unsafe { Some(self.data[self.tail % C].assume_init_mut()) }
+    }
+
+    /// Pops the front element of the queue if the predicate returns true for it. Otherwise, the
+    /// element is left in the queue and None is returned. The predicate is not called if the queue
+    /// is empty.
+    pub fn pop_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        if pred(self.peek()?) { self.pop() } else { None }
+    }
+
+    /// Returns an iterator over references to the next `n` elements that would be popped, without
+    /// removing them. If the queue has fewer than `n` elements, it yields all of them.
+    pub fn peek_n(&self, n: usize) -> Iter<'_, T, C> {
+        self.range_iter(0, n.min(self.len))
+    }
+
+    /// Returns a reference to the front element of the queue, which is the next to be popped.
+    pub fn front(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    /// Returns a reference to the back element of the queue, which is the most recently pushed.
+    pub fn back(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // the element just before head, which wraps to the end of the buffer if head is 0
+        unsafe { Some(self.data[(self.tail + self.len - 1) % C].assume_init_ref()) }
+    }
+
+    /// Returns a mutable reference to the back element of the queue, which is the most recently
+    /// pushed.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        unsafe { Some(self.data[(self.tail + self.len - 1) % C].assume_init_mut()) }
+    }
+
+    /// Returns a reference to the first element of the queue. This is the same as
+    /// [`ArrayQueue::front`].
+    pub fn first(&self) -> Option<&T> {
+        self.front()
+    }
+
+    /// Returns a reference to the last element of the queue. This is the same as
+    /// [`ArrayQueue::back`].
+    pub fn last(&self) -> Option<&T> {
+        self.back()
+    }
+
+    /// Returns a reference to the element at the given index, where index 0 is the front of the
+    /// queue. Returns None if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+
+        unsafe { Some(self.data[(self.tail + index) % C].assume_init_ref()) }
+    }
+
+    /// Returns a mutable reference to the element at the given index, where index 0 is the front
+    /// of the queue. Returns None if the index is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+
+        unsafe { Some(self.data[(self.tail + index) % C].assume_init_mut()) }
+    }
+
+    /// Returns mutable references to the elements at two different indices, where index 0 is the
+    /// front of the queue. Returns None if the indices are equal or either is out of bounds.
+    pub fn get_disjoint_mut(&mut self, i: usize, j: usize) -> Option<(&mut T, &mut T)> {
+        if i == j || i >= self.len || j >= self.len {
+            return None;
+        }
+
+        // distinct logical indices below len map to distinct slots, since len is at most C
+        let (a, b) = ((self.tail + i) % C, (self.tail + j) % C);
+        let (low, high) = self.data.split_at_mut(a.max(b));
+        let (low, high) = unsafe { (low[a.min(b)].assume_init_mut(), high[0].assume_init_mut()) };
+        if a < b { Some((low, high)) } else { Some((high, low)) }
+    }
+
+    /// Replaces the element at the given index, where index 0 is the front of the queue, and
+    /// returns the old element. If the index is out of bounds, the queue is left unchanged, `value`
+    /// is dropped, and None is returned.
+    pub fn replace(&mut self, index: usize, value: T) -> Option<T> {
+        Some(replace(self.get_mut(index)?, value))
+    }
+
+    /// Searches a sorted queue for the given value. Returns `Ok` with the index of a matching
+    /// element, or `Err` with the index where the value could be inserted to keep the queue sorted.
+    /// Index 0 is the front of the queue.
+    pub fn binary_search(&self, value: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        let mut low = 0;
+        let mut high = self.len;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self[mid].cmp(value) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+
+        Err(low)
+    }
+
+    /// Returns the number of elements for which the predicate returns true.
+    pub fn count<P: FnMut(&T) -> bool>(&self, mut pred: P) -> usize {
+        self.iter().filter(|value| pred(value)).count()
+    }
+
+    /// Returns the sum of the elements of the queue.
+    pub fn iter_sum(&self) -> T
+    where
+        T: Copy + Sum,
+    {
+        self.iter().copied().sum()
+    }
+
+    /// Returns a reference to the smallest element of the queue. If several elements are equally
+    /// small, the one closest to the front is returned.
+    ///
+    /// This isn't named `min`, since that would be shadowed by [`Ord::min`] when `T: Ord`.
+    pub fn min_element(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        self.iter().reduce(|min, value| if value < min { value } else { min })
+    }
+
+    /// Returns a reference to the largest element of the queue. If several elements are equally
+    /// large, the one closest to the front is returned.
+    ///
+    /// This isn't named `max`, since that would be shadowed by [`Ord::max`] when `T: Ord`.
+    pub fn max_element(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        self.iter().reduce(|max, value| if value > max { value } else { max })
+    }
+
+    /// Swaps the elements at the given indices, where index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len, "swap index (is {i}) should be < len (is {})", self.len);
+        assert!(j < self.len, "swap index (is {j}) should be < len (is {})", self.len);
+
+        self.data.swap((self.tail + i) % C, (self.tail + j) % C);
+    }
+
+    /// Returns true if the queue contains an element equal to the given value.
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|x| x == value)
+    }
+
+    /// Returns the index of the first element for which the predicate returns true, where index 0
+    /// is the front of the queue. The index can be passed directly to [`ArrayQueue::get`].
+    pub fn position<P: FnMut(&T) -> bool>(&self, pred: P) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    /// Applies `f` to the elements of the queue in FIFO order, and returns the first non-None
+    /// result.
+    pub fn find_map<U, F: FnMut(&T) -> Option<U>>(&self, f: F) -> Option<U> {
+        self.iter().find_map(f)
+    }
+
+    /// Calls `f` on each element of the queue, in FIFO order.
+    pub fn for_each<F: FnMut(&T)>(&self, f: F) {
+        self.iter().for_each(f);
+    }
+
+    /// Calls `f` on each element of the queue in FIFO order, stopping at the first error and
+    /// returning it.
+    pub fn try_for_each<E, F: FnMut(&T) -> Result<(), E>>(&self, f: F) -> Result<(), E> {
+        self.iter().try_for_each(f)
+    }
+
+    /// Inserts an element at the given index, where index 0 is the front of the queue, shifting the
+    /// following elements back. If the queue is full or the index is greater than the length,
+    /// Err(T) is returned.
+    #[must_use = "if the value cannot be inserted, it is returned and must be handled"]
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), T> {
+        if self.is_full() || index > self.len {
+            return Err(value);
+        }
+
+        // push the element at whichever end of the queue is closer, then move it into place
+        if index < self.len - index {
+            let _ = self.push_front(value);
+            for i in 0..index {
+                self.swap(i, i + 1);
+            }
+        } else {
+            let _ = self.push(value);
+            for i in (index..self.len - 1).rev() {
+                self.swap(i, i + 1);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Inserts an element into a sorted queue, at the position that keeps it sorted. If the queue
+    /// is full, Err(T) is returned.
+    #[must_use = "if the queue is full, the value is returned and must be handled"]
+    pub fn insert_sorted(&mut self, value: T) -> Result<(), T>
+    where
+        T: Ord,
+    {
+        let (Ok(index) | Err(index)) = self.binary_search(&value);
+        self.insert(index, value)
+    }
+
+    /// Inserts an element into a sorted queue, at the position that keeps it sorted. If the queue
+    /// is full, the largest element is removed to make room and returned, so the queue keeps the
+    /// `C` smallest elements it has been given. If the new element is the largest, it is returned
+    /// instead.
+    #[must_use = "if the queue is full, the element left out is returned and must be handled"]
+    pub fn insert_sorted_bounded(&mut self, value: T) -> Option<T>
+    where
+        T: Ord,
+    {
+        let evicted = if self.is_full() {
+            match self.back() {
+                Some(back) if value < *back => self.pop_back(),
+                _ => return Some(value),
+            }
+        } else {
+            None
+        };
+
+        // there is always room after evicting
+        let _ = self.insert_sorted(value);
+        evicted
+    }
+
+    /// Removes and returns the element at the given index, where index 0 is the front of the queue,
+    /// keeping the order of the other elements. Returns None if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        // move the element to whichever end of the queue is closer, shifting the elements in
+        // between over by one, then pop it from that end
+        if index < self.len - 1 - index {
+            for i in (0..index).rev() {
+                self.swap(i, i + 1);
+            }
+            self.pop()
+        } else {
+            for i in index..self.len - 1 {
+                self.swap(i, i + 1);
+            }
+            self.pop_back()
+        }
+    }
+
+    /// Removes and returns the `n`th element of the queue, counting from the front like
+    /// [`Iterator::nth`]. Returns None if `n` is out of bounds.
+    ///
+    /// This is the same as [`remove`](Self::remove). The elements between `n` and the nearer end
+    /// of the queue are shifted over to close the gap, which takes O(len) time in the worst case.
+    pub fn pop_nth(&mut self, n: usize) -> Option<T> {
+        self.remove(n)
+    }
+
+    /// Shortens the queue to the first `new_len` elements, dropping the rest. Does nothing if the
+    /// queue is already shorter.
+    pub fn truncate(&mut self, new_len: usize) {
+        while self.len > new_len {
+            drop(self.pop_back());
+        }
+    }
+
+    /// Splits the queue in two at the given index. The elements from `at` onward are moved into
+    /// the returned queue in FIFO order, and the first `at` elements are left in this queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split index (is {at}) should be <= len (is {})", self.len);
+
+        let mut other = Self::new();
+        while self.len > at {
+            // the other queue holds fewer elements than this one did, so every element fits
+            if let Some(value) = self.pop_back() {
+                let _ = other.push_front(value);
+            }
+        }
+        other
+    }
+
+    /// Drops up to `n` elements from the back of the queue, and returns the number of elements
+    /// dropped.
+    pub fn pop_back_n(&mut self, n: usize) -> usize {
+        let count = n.min(self.len);
+        self.truncate(self.len - count);
+        count
+    }
+
+    /// Drops up to `n` elements from the front of the queue, and returns the number of elements
+    /// dropped.
+    ///
+    /// The elements are removed from the queue before any of them is dropped. If dropping an
+    /// element panics, the remaining elements are still dropped.
+    pub fn drop_front(&mut self, n: usize) -> usize {
+        let count = n.min(self.len);
+        let (first, second) = self.slice_ranges(0, count);
+
+        self.tail = (self.tail + count) % C;
+        self.len -= count;
+        self.debug_check_invariants();
+
+        unsafe { drop_ranges(&mut self.data, first, second) };
+        count
+    }
+
+    /// Retains only the elements for which the predicate returns true, preserving their order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|value| f(value));
+    }
+
+    /// Retains only the elements for which the predicate returns true, preserving their order, and
+    /// returns the number of elements removed.
+    pub fn retain_reporting<F: FnMut(&T) -> bool>(&mut self, f: F) -> usize {
+        let old_len = self.len;
+        self.retain(f);
+        old_len - self.len
+    }
+
+    /// Retains only the elements for which the predicate returns true, preserving their order.
+    /// The predicate may modify the elements.
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        // swap each kept element down to the next free logical index, which leaves the removed
+        // elements at the back of the queue. every slot stays initialized, so a panic in the
+        // predicate can't leave the queue in an invalid state
+        let mut kept = 0;
+        for i in 0..self.len {
+            let slot = (self.tail + i) % C;
+            if f(unsafe { self.data[slot].assume_init_mut() }) {
+                self.data.swap((self.tail + kept) % C, slot);
+                kept += 1;
+            }
+        }
+
+        self.truncate(kept);
+    }
+
+    /// Removes consecutive equal elements, keeping the first of each run.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        if self.is_empty() {
+            return;
+        }
+
+        // like `retain`, swap each kept element down to just after the previous kept element,
+        // which leaves the duplicates at the back of the queue
+        let mut kept = 1;
+        for i in 1..self.len {
+            if self[i] != self[kept - 1] {
+                self.swap(kept, i);
+                kept += 1;
+            }
+        }
+
+        self.truncate(kept);
+    }
+
+    /// Returns an iterator over references to the elements of the queue, from front to back.
+    pub fn iter(&self) -> Iter<'_, T, C> {
+        self.range_iter(0, self.len)
+    }
+
+    /// Returns an iterator over the elements of the queue paired with their indices, from front to
+    /// back. The indices are logical, so index 0 is always the element that would be popped next.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter().enumerate()
+    }
+
+    /// Returns an iterator over references to the elements of the queue from logical index `start`
+    /// to the back, skipping the first `start` elements. It yields nothing if `start` is not less
+    /// than the length.
+    pub fn iter_from(&self, start: usize) -> Iter<'_, T, C> {
+        self.range_iter(start.min(self.len), self.len)
+    }
+
+    /// Returns an iterator over references to the elements at logical indices `start..end`.
+    fn range_iter(&self, start: usize, end: usize) -> Iter<'_, T, C> {
+        debug_assert!(end <= self.len);
+
+        let (first, second) = self.slice_ranges(start, end);
+        Iter {
+            first: self.data[first].iter(),
+            second: self.data[second].iter(),
+        }
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` consecutive elements, from front
+    /// to back. Each window is itself an iterator over the elements it covers. There are no
+    /// windows if `size` is greater than the length of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn windows(&self, size: usize) -> Windows<'_, T, C> {
+        assert!(size != 0, "window size must be non-zero");
+        Windows {
+            queue: self,
+            size,
+            start: 0,
+        }
+    }
+
+    /// Returns an iterator over non-overlapping chunks of `size` consecutive elements, from front
+    /// to back. Each chunk is itself an iterator over the elements it covers. The last chunk is
+    /// shorter if `size` does not divide the length of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> Chunks<'_, T, C> {
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks {
+            queue: self,
+            size,
+            start: 0,
+        }
+    }
+
+    /// Returns an iterator over mutable references to the elements of the queue, from front to
+    /// back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, C> {
+        let (first, second) = self.slice_ranges(0, self.len);
+
+        // if the elements wrap, the second range lies entirely before the first, so splitting at
+        // the start of the first range gives two disjoint slices
+        let (front, back) = self.data.split_at_mut(first.start);
+        IterMut {
+            first: back[..first.len()].iter_mut(),
+            second: front[second].iter_mut(),
+        }
+    }
+
+    /// Returns the elements of the queue as a pair of slices, in FIFO order. The second slice is
+    /// empty unless the elements wrap around the end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (first, second) = self.slice_ranges(0, self.len);
+        unsafe {
+            (
+                slice_assume_init_ref(&self.data[first]),
+                slice_assume_init_ref(&self.data[second]),
+            )
+        }
+    }
+
+    /// Returns the elements of the queue as a single slice in FIFO order, or None if they wrap
+    /// around the end of the buffer. In that case, use [`ArrayQueue::as_slices`] or
+    /// [`ArrayQueue::make_contiguous`] instead.
+    pub fn as_contiguous(&self) -> Option<&[T]> {
+        match self.as_slices() {
+            (first, []) => Some(first),
+            _ => None,
+        }
+    }
+
+    /// Rearranges the elements of the queue so that they are contiguous in the buffer, and returns
+    /// them as a single mutable slice in FIFO order.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.is_empty() {
+            self.head = 0;
+            self.tail = 0;
+            return &mut [];
+        }
+
+        // rotating the whole buffer moves the front element to the start without changing the
+        // order of the elements
+        self.data.rotate_left(self.tail % C);
+        self.tail = 0;
+        self.head = self.len;
+        self.debug_check_invariants();
+
+        unsafe { slice_assume_init_mut(&mut self.data[..self.len]) }
+    }
+
+    /// Consumes the queue and returns a queue of the same capacity with `f` applied to each
+    /// element, in FIFO order.
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> ArrayQueue<U, C> {
+        // the new queue has the same capacity, so every element fits
+        self.into_iter().map(f).collect()
+    }
+
+    /// Moves the elements of the queue into a new queue with capacity `C2`, in FIFO order. If they
+    /// don't fit, the queue is returned unchanged in Err(Self).
+    pub fn resize_into<const C2: usize>(self) -> Result<ArrayQueue<T, C2>, Self> {
+        if self.len > C2 {
+            return Err(self);
+        }
+
+        // every element fits, so collecting doesn't drop any
+        Ok(self.into_iter().collect())
+    }
+
+    /// Moves the elements of the queue into a Vec, in FIFO order.
+    #[cfg(feature = "alloc")]
+    pub fn into_vec(self) -> Vec<T> {
+        self.into_iter().collect()
+    }
+
+    /// Copies the elements of the queue into an array, in FIFO order. Returns None if the queue
+    /// doesn't hold exactly `N` elements.
+    pub fn copy_to_array<const N: usize>(&self) -> Option<[T; N]>
+    where
+        T: Copy,
+    {
+        if self.len != N {
+            return None;
+        }
+
+        let mut iter = self.iter().copied();
+        Some(core::array::from_fn(|_| iter.next().unwrap()))
+    }
+
+    /// Reverses the order of the elements of the queue in place, so that the back element becomes
+    /// the front.
+    pub fn reverse(&mut self) {
+        for i in 0..self.len / 2 {
+            self.swap(i, self.len - 1 - i);
+        }
+    }
+
+    /// Rotates the queue `n` places to the left, so that the first `n` elements move to the back.
+    /// If `n` is greater than the length, it is taken modulo the length.
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let n = n % self.len;
+        if self.is_full() {
+            // every slot is live, so moving the front of the queue is enough
+            self.tail = (self.tail + n) % C;
+            self.head = self.tail;
+            self.debug_check_invariants();
+        } else {
+            self.make_contiguous().rotate_left(n);
+        }
+    }
+
+    /// Rotates the queue `n` places to the right, so that the last `n` elements move to the front.
+    /// If `n` is greater than the length, it is taken modulo the length.
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        self.rotate_left(self.len - n % self.len);
+    }
+
+    /// Returns an iterator that removes and yields the elements for which the predicate returns
+    /// true, in FIFO order. The predicate may modify the elements.
+    ///
+    /// The elements that are not removed stay in the queue in their original order. If the
+    /// iterator is dropped before it is exhausted, the remaining elements are kept, whether or not
+    /// they match.
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, C, F> {
+        let old_len = self.len;
+
+        // hide the elements from the queue while the iterator leaves holes between them. if the
+        // iterator is leaked, so are the elements, instead of being dropped twice
+        self.len = 0;
+        self.head = self.tail;
+
+        ExtractIf {
+            queue: self,
+            pred,
+            old_len,
+            index: 0,
+            kept: 0,
+        }
+    }
+
+    /// Returns a cursor pointing to the front element of the queue.
+    pub fn cursor(&mut self) -> Cursor<'_, T, C> {
+        Cursor {
+            queue: self,
+            index: 0,
+        }
+    }
+
+    /// Returns an iterator that pops elements from the queue as it is consumed.
+    ///
+    /// Unlike `Vec::drain`, elements that have not been yielded when the iterator is dropped
+    /// remain in the queue.
+    pub fn drain(&mut self) -> Drain<'_, T, C> {
+        Drain { queue: self }
+    }
+}
+
+/// Drops the elements in two disjoint ranges of slots, where the second range comes before the
+/// first if it is not empty. If dropping an element panics, the other elements are still dropped.
+///
+/// # Safety
+///
+/// Every slot in both ranges must be initialized, and must not be read again.
+unsafe fn drop_ranges<T>(data: &mut [MaybeUninit<T>], first: Range<usize>, second: Range<usize>) {
+    // types without drop glue, such as `Copy` types, don't need the slots touched at all
+    if !mem::needs_drop::<T>() {
+        return;
+    }
+
+    struct Dropper<'a, T>(&'a mut [MaybeUninit<T>]);
+
+    impl<T> Drop for Dropper<'_, T> {
+        fn drop(&mut self) {
+            // dropping a slice keeps going if one of its elements panics
+            unsafe { ptr::drop_in_place(slice_assume_init_mut(self.0)) };
+        }
+    }
+
+    let (front, back) = data.split_at_mut(first.start);
+
+    // locals are dropped in reverse order, so the first range is dropped first, and the second
+    // range is still dropped while unwinding if that panics
+    let _second = Dropper(&mut front[second]);
+    let _first = Dropper(&mut back[..first.len()]);
+}
+
+/// Views a slice of `T` as a slice of initialized slots.
+fn slice_as_uninit<T>(slice: &[T]) -> &[MaybeUninit<T>] {
+    // MaybeUninit<T> has the same layout as T, and the slice can't be written through
+    unsafe { &*(slice as *const [T] as *const [MaybeUninit<T>]) }
+}
+
+/// Views a slice of initialized slots as a slice of `T`.
+///
+/// # Safety
+///
+/// Every slot in `slice` must be initialized.
+unsafe fn slice_assume_init_ref<T>(slice: &[MaybeUninit<T>]) -> &[T] {
+    // MaybeUninit<T> has the same layout as T
+    unsafe { &*(slice as *const [MaybeUninit<T>] as *const [T]) }
+}
+
+/// Views a mutable slice of initialized slots as a mutable slice of `T`.
+///
+/// # Safety
+///
+/// Every slot in `slice` must be initialized.
+unsafe fn slice_assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    unsafe { &mut *(slice as *mut [MaybeUninit<T>] as *mut [T]) }
+}
+
+impl<T, const C: usize> Drop for ArrayQueue<T, C> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Clone, const C: usize> Clone for ArrayQueue<T, C> {
+    fn clone(&self) -> Self {
+        // only the live elements are cloned; the clone starts at the beginning of its buffer
+        let mut queue = Self::new();
+        for value in self.iter() {
+            let _ = queue.push(value.clone());
+        }
+        queue
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        // clone over the elements both queues have, then drop or clone the remainder
+        self.truncate(source.len);
+        for (dst, src) in self.iter_mut().zip(source.iter()) {
+            dst.clone_from(src);
+        }
+        for value in source.iter().skip(self.len) {
+            let _ = self.push(value.clone());
+        }
+    }
+}
+
+impl<T: fmt::Debug, const C: usize> fmt::Debug for ArrayQueue<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // only the live elements are printed; the other slots are uninitialized
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Formats the elements of the queue in FIFO order, separated by commas and enclosed in square
+/// brackets.
+impl<T: fmt::Display, const C: usize> fmt::Display for ArrayQueue<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            value.fmt(f)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<T: PartialEq, const C: usize> PartialEq for ArrayQueue<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        // compare the logical contents, regardless of where they sit in the buffer
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const C: usize> Eq for ArrayQueue<T, C> {}
+
+/// Compares the elements of the queues lexicographically, in FIFO order.
+impl<T: PartialOrd, const C: usize> PartialOrd for ArrayQueue<T, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+/// Compares the elements of the queues lexicographically, in FIFO order.
+impl<T: Ord, const C: usize> Ord for ArrayQueue<T, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: PartialEq, const C: usize> PartialEq<[T]> for ArrayQueue<T, C> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.len == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: PartialEq, const C: usize> PartialEq<&[T]> for ArrayQueue<T, C> {
+    fn eq(&self, other: &&[T]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T: PartialEq, const C: usize, const N: usize> PartialEq<[T; N]> for ArrayQueue<T, C> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T, const C: usize> Index<usize> for ArrayQueue<T, C> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len;
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+impl<T, const C: usize> IndexMut<usize> for ArrayQueue<T, C> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {len} but the index is {index}")
+        })
+    }
+}
+
+/// Collects elements into a queue until it is full. Any further elements are discarded.
+impl<T, const C: usize> FromIterator<T> for ArrayQueue<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+/// Pushs elements to the queue until it is full. Any further elements are discarded.
+impl<T, const C: usize> Extend<T> for ArrayQueue<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            if self.push(value).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+impl<T: Hash, const C: usize> Hash for ArrayQueue<T, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // hash the logical contents, to agree with `PartialEq`
+        self.len.hash(state);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+impl<T, const C: usize> IntoIterator for ArrayQueue<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { queue: self }
+    }
+}
+
+/// Moves the elements of an array into a queue, in order. Fails and returns the array if it has more
+/// than `C` elements.
+impl<T, const C: usize, const N: usize> TryFrom<[T; N]> for ArrayQueue<T, C> {
+    type Error = [T; N];
+
+    fn try_from(arr: [T; N]) -> Result<Self, Self::Error> {
+        if N > C {
+            return Err(arr);
+        }
+
+        Ok(arr.into_iter().collect())
+    }
+}
+
+/// Clones the elements of a slice into a queue, in order. Fails if the slice has more than `C`
+/// elements.
+impl<T: Clone, const C: usize> TryFrom<&[T]> for ArrayQueue<T, C> {
+    type Error = CapacityError;
+
+    fn try_from(src: &[T]) -> Result<Self, Self::Error> {
+        Self::try_from_slice(src)
+    }
+}
+
+/// Moves the elements of a Vec into a queue, in order. Fails and returns the Vec if it has more than
+/// `C` elements.
+#[cfg(feature = "alloc")]
+impl<T, const C: usize> TryFrom<Vec<T>> for ArrayQueue<T, C> {
+    type Error = Vec<T>;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        if vec.len() > C {
+            return Err(vec);
+        }
+
+        Ok(vec.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const C: usize> From<ArrayQueue<T, C>> for VecDeque<T> {
+    fn from(queue: ArrayQueue<T, C>) -> Self {
+        queue.into_iter().collect()
+    }
+}
+
+/// Writes bytes to the back of the queue. A write pushes as many bytes as fit and never fails, so
+/// it returns Ok(0) when the queue is full.
+#[cfg(feature = "std")]
+impl<const C: usize> std::io::Write for ArrayQueue<u8, C> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(self.push_slice(buf))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads bytes from the front of the queue. A read pops as many bytes as the buffer holds and never
+/// fails, so it returns Ok(0) when the queue is empty.
+#[cfg(feature = "std")]
+impl<const C: usize> std::io::Read for ArrayQueue<u8, C> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.pop_slice(buf))
+    }
+}
+
+/// An iterator that removes and yields the elements of an [`ArrayQueue`] that match a predicate.
+///
+/// Created by [`ArrayQueue::extract_if`].
+pub struct ExtractIf<'a, T, const C: usize, F> {
+    queue: &'a mut ArrayQueue<T, C>,
+    pred: F,
+
+    // the length of the queue before the iterator was created
+    old_len: usize,
+
+    // the logical index of the next element to check
+    index: usize,
+
+    // the number of elements kept so far, which sit at logical indices 0..kept
+    kept: usize,
+}
+
+impl<T, const C: usize, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'_, T, C, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.old_len {
+            let slot = (self.queue.tail + self.index) % C;
+            let extract = (self.pred)(unsafe { self.queue.data[slot].assume_init_mut() });
+
+            // only advance after the predicate returns, so that the element is kept if it panics
+            self.index += 1;
+
+            if extract {
+                // this leaves a hole, which the next kept element is swapped into
+                return Some(unsafe { self.queue.data[slot].assume_init_read() });
+            }
+
+            self.queue.data.swap((self.queue.tail + self.kept) % C, slot);
+            self.kept += 1;
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.index))
+    }
+}
+
+impl<T, const C: usize, F> Drop for ExtractIf<'_, T, C, F> {
+    fn drop(&mut self) {
+        // move the unchecked elements down to just after the kept elements, closing the holes
+        let tail = self.queue.tail;
+        let holes = self.index - self.kept;
+        for i in self.index..self.old_len {
+            self.queue.data.swap((tail + i - holes) % C, (tail + i) % C);
+        }
+
+        self.queue.len = self.old_len - holes;
+        self.queue.head = (tail + self.queue.len) % C;
+        self.queue.debug_check_invariants();
+    }
+}
+
+/// A cursor over an [`ArrayQueue`] that can move in both directions and edit or remove the element
+/// it points to.
+///
+/// Besides the elements, the cursor can point to a "ghost" position that sits past the back and
+/// before the front of the queue, like the cursors of `LinkedList`. This is also where it points if
+/// the queue is empty.
+///
+/// Created by [`ArrayQueue::cursor`].
+pub struct Cursor<'a, T, const C: usize> {
+    queue: &'a mut ArrayQueue<T, C>,
+    index: usize,
+}
+
+impl<T, const C: usize> Cursor<'_, T, C> {
+    /// Returns the index of the element the cursor points to, where index 0 is the front of the
+    /// queue. Returns None at the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.queue.len()).then_some(self.index)
+    }
+
+    /// Moves the cursor to the next element. Moves from the back element to the ghost position, and
+    /// from the ghost position to the front element.
+    pub fn move_next(&mut self) {
+        self.index = if self.index >= self.queue.len() { 0 } else { self.index + 1 };
+    }
+
+    /// Moves the cursor to the previous element. Moves from the front element to the ghost
+    /// position, and from the ghost position to the back element.
+    pub fn move_prev(&mut self) {
+        self.index = if self.index == 0 { self.queue.len() } else { self.index - 1 };
+    }
+
+    /// Returns a mutable reference to the element the cursor points to, or None at the ghost
+    /// position.
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.queue.get_mut(self.index)
+    }
+
+    /// Removes and returns the element the cursor points to, leaving the cursor pointing to the
+    /// element after it. Returns None at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.queue.remove(self.index)
+    }
+}
+
+/// An iterator over references to the elements of an [`ArrayQueue`], from front to back.
+///
+/// Created by [`ArrayQueue::iter`].
+pub struct Iter<'a, T, const C: usize> {
+    first: slice::Iter<'a, MaybeUninit<T>>,
+    second: slice::Iter<'a, MaybeUninit<T>>,
+}
+
+impl<'a, T, const C: usize> Iterator for Iter<'a, T, C> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = self.first.next().or_else(|| self.second.next())?;
+
+        // the iterator only covers initialized slots
+        unsafe { Some(slot.assume_init_ref()) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const C: usize> DoubleEndedIterator for Iter<'_, T, C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let slot = self.second.next_back().or_else(|| self.first.next_back())?;
+
+        // the iterator only covers initialized slots
+        unsafe { Some(slot.assume_init_ref()) }
+    }
+}
+
+impl<T, const C: usize> ExactSizeIterator for Iter<'_, T, C> {}
+
+impl<T, const C: usize> FusedIterator for Iter<'_, T, C> {}
+
+/// An iterator over overlapping windows of elements of an [`ArrayQueue`], from front to back.
+///
+/// Created by [`ArrayQueue::windows`].
+pub struct Windows<'a, T, const C: usize> {
+    queue: &'a ArrayQueue<T, C>,
+    size: usize,
+    start: usize,
+}
+
+impl<'a, T, const C: usize> Iterator for Windows<'a, T, C> {
+    type Item = Iter<'a, T, C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.start + self.size;
+        if end > self.queue.len() {
+            return None;
+        }
+
+        let window = self.queue.range_iter(self.start, end);
+        self.start += 1;
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.queue.len() + 1).saturating_sub(self.start + self.size);
+        (len, Some(len))
+    }
+}
+
+impl<T, const C: usize> ExactSizeIterator for Windows<'_, T, C> {}
+
+impl<T, const C: usize> FusedIterator for Windows<'_, T, C> {}
+
+/// An iterator over non-overlapping chunks of elements of an [`ArrayQueue`], from front to back.
+///
+/// Created by [`ArrayQueue::chunks`].
+pub struct Chunks<'a, T, const C: usize> {
+    queue: &'a ArrayQueue<T, C>,
+    size: usize,
+    start: usize,
+}
+
+impl<'a, T, const C: usize> Iterator for Chunks<'a, T, C> {
+    type Item = Iter<'a, T, C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.queue.len() {
+            return None;
+        }
+
+        let end = (self.start + self.size).min(self.queue.len());
+        let chunk = self.queue.range_iter(self.start, end);
+        self.start = end;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.queue.len() - self.start).div_ceil(self.size);
+        (len, Some(len))
+    }
+}
+
+impl<T, const C: usize> ExactSizeIterator for Chunks<'_, T, C> {}
+
+impl<T, const C: usize> FusedIterator for Chunks<'_, T, C> {}
+
+/// An iterator over mutable references to the elements of an [`ArrayQueue`], from front to back.
+///
+/// Created by [`ArrayQueue::iter_mut`].
+pub struct IterMut<'a, T, const C: usize> {
+    first: slice::IterMut<'a, MaybeUninit<T>>,
+    second: slice::IterMut<'a, MaybeUninit<T>>,
+}
+
+impl<'a, T, const C: usize> Iterator for IterMut<'a, T, C> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = self.first.next().or_else(|| self.second.next())?;
+
+        // the iterator only covers initialized slots
+        unsafe { Some(slot.assume_init_mut()) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const C: usize> ExactSizeIterator for IterMut<'_, T, C> {}
+
+impl<T, const C: usize> FusedIterator for IterMut<'_, T, C> {}
+
+/// An owning iterator over the elements of an [`ArrayQueue`], from front to back.
+///
+/// Elements that have not been yielded are dropped along with the iterator.
+///
+/// Created by the [`IntoIterator`] implementation of [`ArrayQueue`].
+pub struct IntoIter<T, const C: usize> {
+    queue: ArrayQueue<T, C>,
+}
+
+impl<T, const C: usize> Iterator for IntoIter<T, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // popping moves each element out of the queue, so the queue's own `Drop` only sees the
+        // elements that were never yielded
+        self.queue.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<T, const C: usize> ExactSizeIterator for IntoIter<T, C> {}
+
+impl<T, const C: usize> FusedIterator for IntoIter<T, C> {}
+
+/// An iterator that pops elements from an [`ArrayQueue`], from front to back.
+///
+/// Created by [`ArrayQueue::drain`].
+pub struct Drain<'a, T, const C: usize> {
+    queue: &'a mut ArrayQueue<T, C>,
+}
+
+impl<T, const C: usize> Iterator for Drain<'_, T, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<T, const C: usize> ExactSizeIterator for Drain<'_, T, C> {}
+
+impl<T, const C: usize> FusedIterator for Drain<'_, T, C> {}
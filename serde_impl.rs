@@ -0,0 +1,44 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::ArrayQueue;
+
+/// Serializes the elements of the queue as a sequence, from front to back.
+impl<T: Serialize, const C: usize> Serialize for ArrayQueue<T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// Deserializes a sequence of elements, pushing them in order. Fails if the sequence has more than
+/// `C` elements.
+impl<'de, T: Deserialize<'de>, const C: usize> Deserialize<'de> for ArrayQueue<T, C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(QueueVisitor(PhantomData))
+    }
+}
+
+struct QueueVisitor<T, const C: usize>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>, const C: usize> Visitor<'de> for QueueVisitor<T, C> {
+    type Value = ArrayQueue<T, C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of at most {C} elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut queue = ArrayQueue::new();
+        while let Some(value) = seq.next_element()? {
+            if queue.push(value).is_err() {
+                // the rest of the sequence isn't read, so its real length is unknown
+                return Err(de::Error::custom(format_args!("sequence has more than {C} elements")));
+            }
+        }
+
+        Ok(queue)
+    }
+}
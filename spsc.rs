@@ -0,0 +1,136 @@
+//! A lock-free queue for passing elements from one producer thread to one consumer thread.
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::ArrayQueue;
+
+/// A ring buffer like [`ArrayQueue`] whose head and tail pointers are atomic, so that it can be
+/// split into a [`Producer`] and a [`Consumer`] that are used from different threads.
+pub struct Queue<T, const C: usize> {
+    data: [UnsafeCell<MaybeUninit<T>>; C],
+
+    // head and tail count up to 2 * C before wrapping, so that a full queue (head is C ahead of
+    // tail) can be told apart from an empty one (head equals tail). the producer is the only
+    // writer of head, and the consumer is the only writer of tail
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+impl<T, const C: usize> Default for Queue<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const C: usize> Queue<T, C> {
+    /// Creates a new empty Queue.
+    pub const fn new() -> Self {
+        let () = ArrayQueue::<T, C>::NONZERO_CAPACITY;
+        Self {
+            data: [const { UnsafeCell::new(MaybeUninit::uninit()) }; C],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the queue into its producer and consumer halves. Elements pushed through the
+    /// producer are popped through the consumer in FIFO order.
+    pub fn split(&mut self) -> (Producer<'_, T, C>, Consumer<'_, T, C>) {
+        (Producer { queue: self }, Consumer { queue: self })
+    }
+
+    /// Returns the number of elements between the given tail and head pointers.
+    fn distance(head: usize, tail: usize) -> usize {
+        if head >= tail { head - tail } else { head + 2 * C - tail }
+    }
+
+    /// Advances a head or tail pointer by one slot.
+    fn increment(index: usize) -> usize {
+        if index + 1 == 2 * C { 0 } else { index + 1 }
+    }
+}
+
+impl<T, const C: usize> Drop for Queue<T, C> {
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        let mut tail = *self.tail.get_mut();
+        while tail != head {
+            unsafe { self.data[tail % C].get_mut().assume_init_drop() };
+            tail = Self::increment(tail);
+        }
+    }
+}
+
+/// Moves the elements of an [`ArrayQueue`] into a new Queue, in FIFO order.
+impl<T, const C: usize> From<ArrayQueue<T, C>> for Queue<T, C> {
+    fn from(queue: ArrayQueue<T, C>) -> Self {
+        let mut spsc = Self::new();
+        let (mut producer, _) = spsc.split();
+        for value in queue {
+            // the queues have the same capacity, so every element fits
+            let _ = producer.push(value);
+        }
+        spsc
+    }
+}
+
+/// The half of a split [`Queue`] that pushes elements.
+pub struct Producer<'a, T, const C: usize> {
+    queue: &'a Queue<T, C>,
+}
+
+// the producer only writes to slots that the consumer is not reading, and hands them over with
+// release / acquire ordering on head
+unsafe impl<T: Send, const C: usize> Send for Producer<'_, T, C> {}
+
+impl<T, const C: usize> Producer<'_, T, C> {
+    /// Pushs an element to the queue. If the queue is full, Err(T) is returned.
+    #[must_use = "if the queue is full, the value is returned and must be handled"]
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+
+        // acquire, so that the consumer is done reading the slot before it is overwritten
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if Queue::<T, C>::distance(head, tail) == C {
+            return Err(value);
+        }
+
+        unsafe { (*self.queue.data[head % C].get()).write(value) };
+
+        // release, so that the consumer sees the element once it sees the new head
+        self.queue.head.store(Queue::<T, C>::increment(head), Ordering::Release);
+
+        Ok(())
+    }
+}
+
+/// The half of a split [`Queue`] that pops elements.
+pub struct Consumer<'a, T, const C: usize> {
+    queue: &'a Queue<T, C>,
+}
+
+// the consumer only reads from slots that the producer has finished writing, and hands them back
+// with release / acquire ordering on tail
+unsafe impl<T: Send, const C: usize> Send for Consumer<'_, T, C> {}
+
+impl<T, const C: usize> Consumer<'_, T, C> {
+    /// Pops an element from the queue.
+    pub fn pop(&mut self) -> Option<T> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+
+        // acquire, so that the element written by the producer is visible
+        let head = self.queue.head.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        let value = unsafe { (*self.queue.data[tail % C].get()).assume_init_read() };
+
+        // release, so that the producer only reuses the slot after it has been read
+        self.queue.tail.store(Queue::<T, C>::increment(tail), Ordering::Release);
+
+        Some(value)
+    }
+}
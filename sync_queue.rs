@@ -0,0 +1,101 @@
+//! A queue that can be shared between threads, guarded by a [`Mutex`].
+
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+use crate::ArrayQueue;
+
+/// An [`ArrayQueue`] behind a mutex, which can be pushed to and popped from through a shared
+/// reference. Wrap it in an [`Arc`](std::sync::Arc) to share it between threads.
+///
+/// Every method locks the queue for the duration of the call. For a single producer and a single
+/// consumer, the lock-free [`spsc::Queue`](crate::spsc::Queue) avoids the lock entirely.
+pub struct SyncQueue<T, const C: usize> {
+    inner: Mutex<ArrayQueue<T, C>>,
+}
+
+impl<T, const C: usize> Default for SyncQueue<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const C: usize> SyncQueue<T, C> {
+    /// Creates a new empty SyncQueue.
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(ArrayQueue::new()),
+        }
+    }
+
+    /// Locks the queue. A panic while the lock was held can't leave the queue in an invalid state,
+    /// so poisoning is ignored.
+    fn lock(&self) -> MutexGuard<'_, ArrayQueue<T, C>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the queue if no other thread holds the lock.
+    fn try_lock(&self) -> Option<MutexGuard<'_, ArrayQueue<T, C>>> {
+        match self.inner.try_lock() {
+            Ok(queue) => Some(queue),
+            Err(TryLockError::Poisoned(err)) => Some(err.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    /// Pushs an element to the queue, waiting for the lock. If the queue is full, Err(T) is
+    /// returned.
+    #[must_use = "if the queue is full, the value is returned and must be handled"]
+    pub fn push(&self, value: T) -> Result<(), T> {
+        self.lock().push(value)
+    }
+
+    /// Pops an element from the queue, waiting for the lock.
+    pub fn pop(&self) -> Option<T> {
+        self.lock().pop()
+    }
+
+    /// Pushs an element to the queue without waiting. If the queue is full or another thread holds
+    /// the lock, Err(T) is returned.
+    #[must_use = "if the queue is full or locked, the value is returned and must be handled"]
+    pub fn try_push(&self, value: T) -> Result<(), T> {
+        match self.try_lock() {
+            Some(mut queue) => queue.push(value),
+            None => Err(value),
+        }
+    }
+
+    /// Pops an element from the queue without waiting. Returns None if the queue is empty or
+    /// another thread holds the lock.
+    pub fn try_pop(&self) -> Option<T> {
+        self.try_lock()?.pop()
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns true if the queue is full.
+    pub fn is_full(&self) -> bool {
+        self.lock().is_full()
+    }
+
+    /// Consumes the SyncQueue and returns the queue inside it.
+    pub fn into_inner(self) -> ArrayQueue<T, C> {
+        self.inner.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Moves an [`ArrayQueue`] into a new SyncQueue.
+impl<T, const C: usize> From<ArrayQueue<T, C>> for SyncQueue<T, C> {
+    fn from(queue: ArrayQueue<T, C>) -> Self {
+        Self {
+            inner: Mutex::new(queue),
+        }
+    }
+}